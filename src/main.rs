@@ -1,120 +1,272 @@
 use axum::{
-    http::StatusCode,
-    extract::{Path, State},
-    routing::{get, post, delete},
-    Json,
-    Router,
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json, Router,
 };
+use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
-use std::sync::{Arc, Mutex};
-use std::vec::Vec;
+use serde_json::json;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+use tower_http::cors::{Any, CorsLayer};
+use tower_http::trace::TraceLayer;
+use uuid::Uuid;
 
-// We'll create a unique ID for each user.
-static mut NEXT_ID: u32 = 0;
+mod router;
 
-// The struct for incoming JSON data. We add an 'id' field to uniquely identify users
+// The embedded schema, applied at startup so a fresh database is usable
+// without an external migration step.
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS users (
+    id TEXT PRIMARY KEY,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    name TEXT NOT NULL UNIQUE,
+    age INTEGER NOT NULL,
+    password TEXT NOT NULL
+);";
+
+// The crate-wide error type. Every fallible handler returns `Result<_, Error>`
+// and uses `?`, so no task ever panics on a failed query or a missing header.
+#[derive(thiserror::Error, Debug)]
+enum Error {
+    #[error("resource not found")]
+    NotFound,
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("{0}")]
+    Validation(String),
+    #[error("authentication required")]
+    Auth,
+    #[error("internal server error")]
+    Internal,
+}
+
+// Shorthand so handlers can write `Result<Json<T>>`.
+type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = match self {
+            Error::NotFound => StatusCode::NOT_FOUND,
+            Error::Validation(_) => StatusCode::BAD_REQUEST,
+            Error::Auth => StatusCode::UNAUTHORIZED,
+            Error::Database(_) | Error::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
+
+// The account model. The `password` field holds the Argon2 hash and is never
+// serialized back out to clients.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 struct UserData {
-    // The ID field is optional for the incoming JSON from POST requests
-    // It will be assigned by the server.
+    // Generated server-side with `Uuid::new_v4()`, so ids are collision-free
+    // without any coordination and never need a shared counter.
     #[serde(default)]
-    id: u32,
+    id: Uuid,
     name: String,
-    age: u32,
+    age: i64,
+    #[serde(skip_serializing)]
+    password: String,
 }
 
-// The application's state to hold our in-memory database
-struct AppState {
-    users: Arc<Mutex<Vec<UserData>>>,
+// The `id` column is stored as 36-char `TEXT`, so it must be decoded through a
+// `String` and parsed back into a `Uuid` — sqlx's built-in `Decode for Uuid`
+// expects a 16-byte blob and would fail on the text value.
+impl<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> for UserData {
+    fn from_row(row: &'r sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id: String = row.try_get("id")?;
+        let id = Uuid::parse_str(&id).map_err(|e| sqlx::Error::ColumnDecode {
+            index: "id".to_string(),
+            source: Box::new(e),
+        })?;
+
+        Ok(Self {
+            id,
+            name: row.try_get("name")?,
+            age: row.try_get("age")?,
+            password: row.try_get("password")?,
+        })
+    }
 }
 
-//---
+// Payload for updating an existing user in place.
+#[derive(Deserialize)]
+struct UpdateUser {
+    name: String,
+    age: i64,
+}
 
-// Main function to run the application.
-#[tokio::main]
-async fn main() {
-    // Initialize the application state with an empty vector.
-    let app_state = Arc::new(Mutex::new(Vec::new()));
+// Payload for registering or logging in a user.
+#[derive(Deserialize)]
+struct Credentials {
+    name: String,
+    #[serde(default)]
+    age: i64,
+    password: String,
+}
 
-    // Define the application routes and attach the shared state.
-    let app = Router::new()
-        .route("/", get(hello_world))
-        .route("/greet/:name", get(greet_person))
-        .route("/users", post(add_user).get(list_users))
-        .route("/users/:id", delete(delete_user))
-        .with_state(app_state);
-
-    // Set up the server address.
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
-    println!("Server running on http://{}", addr);
+// The JWT claims: which user the token belongs to and when it expires.
+#[derive(Deserialize, Serialize)]
+struct Claims {
+    sub: Uuid,
+    exp: usize,
+}
 
-    // Start the server.
-    axum::Server::bind(&addr)
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
+// The authenticated user id, produced by verifying the bearer token. Handlers
+// can take this as an argument to require a valid token.
+struct AuthUser(Uuid);
+
+// Runtime settings, sourced from the environment so `main` never hard-codes a
+// bind address. Everything but the JWT secret has a default; the secret does not.
+#[derive(Clone)]
+struct Config {
+    database_url: String,
+    bind_addr: std::net::SocketAddr,
+    jwt_secret: String,
+    jwt_maxage: i64,
+    cors_origin: String,
 }
 
+impl Config {
+    // Read and validate the configuration from the environment.
+    fn init() -> Self {
+        let database_url =
+            std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:users.db?mode=rwc".to_string());
+        let host = std::env::var("BIND_ADDR").unwrap_or_else(|_| "0.0.0.0".to_string());
+        let port: u16 = std::env::var("PORT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3000);
+        let bind_addr = format!("{}:{}", host, port)
+            .parse()
+            .expect("BIND_ADDR/PORT do not form a valid socket address");
+        // No default: signing tokens with a guessable secret is never safe, so
+        // an unconfigured deployment must fail to boot rather than silently use
+        // a publicly-known value.
+        let jwt_secret = std::env::var("JWT_SECRET")
+            .expect("JWT_SECRET must be set to a non-empty, non-default secret");
+        let jwt_maxage: i64 = std::env::var("JWT_MAXAGE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+        let cors_origin = std::env::var("CORS_ORIGIN").unwrap_or_else(|_| "*".to_string());
 
+        assert!(!jwt_secret.is_empty(), "JWT_SECRET must not be empty");
+        assert!(
+            jwt_secret != "change-me",
+            "JWT_SECRET must not be the placeholder value \"change-me\""
+        );
+        assert!(jwt_maxage > 0, "JWT_MAXAGE must be positive");
 
-// Handler for the root route
-async fn hello_world() -> &'static str {
-    "Hello, world!"
+        Self {
+            database_url,
+            bind_addr,
+            jwt_secret,
+            jwt_maxage,
+            cors_origin,
+        }
+    }
 }
 
-// Handler to greet a person based on a URL parameter.
-async fn greet_person(Path(name): Path<String>) -> String {
-    format!("Hello, {}!", name)
+// The application's state, now backed by a connection pool so users survive
+// restarts and handlers no longer contend on a single global mutex.
+#[derive(Clone)]
+struct AppState {
+    pool: SqlitePool,
+    config: Config,
 }
 
-// Handler to add a new user to our in-memory database.
-async fn add_user(
-    State(users_state): State<Arc<Mutex<Vec<UserData>>>>,
-    Json(mut payload): Json<UserData>,
-) -> Json<UserData> {
-    let mut users = users_state.lock().unwrap();
-    
-    // Assign a unique ID to the new user.
-    unsafe {
-        payload.id = NEXT_ID;
-        NEXT_ID += 1;
-    }
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self> {
+        let app_state = AppState::from_ref(state);
 
-    // Add the new user to the list.
-    users.push(payload.clone());
+        // Pull the `Authorization: Bearer <token>` header.
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(Error::Auth)?;
 
-    println!("New user added. Total users: {}", users.len());
-    
-    Json(payload)
+        // Verify the signature and expiry.
+        let data = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(app_state.config.jwt_secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|_| Error::Auth)?;
+
+        Ok(AuthUser(data.claims.sub))
+    }
 }
 
-// Handler to list all users as JSON.
-async fn list_users(
-    State(users_state): State<Arc<Mutex<Vec<UserData>>>>,
-) -> Json<Vec<UserData>> {
-    let users = users_state.lock().unwrap();
-    
-    Json(users.clone())
+// Build the CORS layer from configuration: `*` is fully permissive, otherwise
+// only the named origin is allowed.
+fn cors_layer(origin: &str) -> CorsLayer {
+    if origin == "*" {
+        CorsLayer::new()
+            .allow_origin(Any)
+            .allow_methods(Any)
+            .allow_headers(Any)
+    } else {
+        let value: HeaderValue = origin.parse().expect("CORS_ORIGIN is not a valid header value");
+        CorsLayer::new()
+            .allow_origin(value)
+            .allow_methods(Any)
+            .allow_headers(Any)
+    }
 }
 
-// New handler for deleting a user by ID with professional error handling
-async fn delete_user(
-    State(users_state): State<Arc<Mutex<Vec<UserData>>>>,
-    Path(id): Path<u32>,
-) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
-    let mut users = users_state.lock().unwrap();
+//---
 
-    let initial_len = users.len();
-    users.retain(|user| user.id != id);
+// Main function to run the application.
+#[tokio::main]
+async fn main() {
+    // Load and validate the configuration from the environment.
+    let config = Config::init();
 
-    // Check if the user was actually removed.
-    if users.len() < initial_len {
-        Ok(format!("User with ID {} successfully deleted.", id))
-    } else {
-        // If not found, return a 404 Not Found status with a JSON error.
-        let error_message = format!("User with ID {} not found.", id);
-        let json_error = serde_json::json!({ "error": error_message });
-        Err((StatusCode::NOT_FOUND, Json(json_error)))
-    }
-}
\ No newline at end of file
+    // Set up structured request/response logging.
+    tracing_subscriber::fmt::init();
+
+    // Connect to the database and apply the embedded schema.
+    let pool = SqlitePoolOptions::new()
+        .connect(&config.database_url)
+        .await
+        .expect("failed to connect to the database");
+    sqlx::query(SCHEMA)
+        .execute(&pool)
+        .await
+        .expect("failed to apply the schema");
+
+    let app_state = AppState { pool, config };
+
+    // Merge the per-resource routers and layer on tracing and CORS.
+    let app = Router::new()
+        .merge(router::health::get_health_router())
+        .merge(router::auth::get_auth_router())
+        .merge(router::users::get_users_router())
+        .layer(TraceLayer::new_for_http())
+        .layer(cors_layer(&app_state.config.cors_origin))
+        .with_state(app_state.clone());
+
+    // Take the server address from the configuration.
+    let addr = app_state.config.bind_addr;
+    println!("Server running on http://{}", addr);
+
+    // Start the server.
+    axum::Server::bind(&addr)
+        .serve(app.into_make_service())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,90 @@
+use argon2::password_hash::{
+    rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString,
+};
+use argon2::Argon2;
+use axum::{
+    extract::State,
+    routing::post,
+    Json, Router,
+};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{AppState, Claims, Credentials, Error, Result, UserData};
+
+// Registration and login routes.
+pub fn get_auth_router() -> Router<AppState> {
+    Router::new()
+        .route("/register", post(register))
+        .route("/login", post(login))
+}
+
+// Handler to register a new account, hashing the password with Argon2 before
+// it ever touches the database.
+async fn register(
+    State(state): State<AppState>,
+    Json(payload): Json<Credentials>,
+) -> Result<Json<UserData>> {
+    let salt = SaltString::generate(&mut OsRng);
+    let password = Argon2::default()
+        .hash_password(payload.password.as_bytes(), &salt)
+        .map_err(|_| Error::Internal)?
+        .to_string();
+
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, name, age, password) VALUES (?, ?, ?, ?)")
+        .bind(id.to_string())
+        .bind(&payload.name)
+        .bind(payload.age)
+        .bind(&password)
+        .execute(&state.pool)
+        .await
+        .map_err(|e| match e {
+            // The `name` column is UNIQUE; a conflict means the name is taken.
+            sqlx::Error::Database(ref db) if db.message().contains("UNIQUE") => {
+                Error::Validation("name already taken".to_string())
+            }
+            other => Error::Database(other),
+        })?;
+
+    Ok(Json(UserData {
+        id,
+        name: payload.name,
+        age: payload.age,
+        password,
+    }))
+}
+
+// Handler to log in: verify the password against the stored hash and, on
+// success, sign a JWT carrying the user id and an expiry.
+async fn login(
+    State(state): State<AppState>,
+    Json(payload): Json<Credentials>,
+) -> Result<Json<serde_json::Value>> {
+    let user =
+        sqlx::query_as::<_, UserData>("SELECT id, name, age, password FROM users WHERE name = ?")
+            .bind(&payload.name)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(Error::Auth)?;
+
+    let parsed = PasswordHash::new(&user.password).map_err(|_| Error::Auth)?;
+    Argon2::default()
+        .verify_password(payload.password.as_bytes(), &parsed)
+        .map_err(|_| Error::Auth)?;
+
+    let exp = (chrono::Utc::now().timestamp() + state.config.jwt_maxage) as usize;
+    let claims = Claims {
+        sub: user.id,
+        exp,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(state.config.jwt_secret.as_bytes()),
+    )
+    .map_err(|_| Error::Internal)?;
+
+    Ok(Json(json!({ "token": token })))
+}
@@ -0,0 +1,24 @@
+use axum::{
+    extract::Path,
+    routing::get,
+    Router,
+};
+
+use crate::AppState;
+
+// Liveness and greeting routes that don't touch a resource.
+pub fn get_health_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(hello_world))
+        .route("/greet/:name", get(greet_person))
+}
+
+// Handler for the root route.
+async fn hello_world() -> &'static str {
+    "Hello, world!"
+}
+
+// Handler to greet a person based on a URL parameter.
+async fn greet_person(Path(name): Path<String>) -> String {
+    format!("Hello, {}!", name)
+}
@@ -0,0 +1,5 @@
+// Per-resource routers. Each submodule owns its handlers and exposes a single
+// `get_*_router()` that returns a `Router<AppState>`, which `main` merges.
+pub mod auth;
+pub mod health;
+pub mod users;
@@ -0,0 +1,90 @@
+use axum::{
+    extract::{Path, State},
+    routing::get,
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::{AppState, AuthUser, Error, Result, UpdateUser, UserData};
+
+// The `/users` resource. Account creation lives on `/register`, so this router
+// only lists, fetches, updates, and deletes existing users.
+pub fn get_users_router() -> Router<AppState> {
+    Router::new()
+        .route("/users", get(list_users))
+        .route(
+            "/users/:id",
+            get(get_user).put(update_user).delete(delete_user),
+        )
+}
+
+// Handler to list all users as JSON.
+async fn list_users(State(state): State<AppState>) -> Result<Json<Vec<UserData>>> {
+    let users = sqlx::query_as::<_, UserData>("SELECT id, name, age, password FROM users")
+        .fetch_all(&state.pool)
+        .await?;
+
+    Ok(Json(users))
+}
+
+// Handler to fetch a single user by ID, 404-ing through the error type if the
+// id is unknown.
+async fn get_user(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<UserData>> {
+    let user = sqlx::query_as::<_, UserData>("SELECT id, name, age, password FROM users WHERE id = ?")
+        .bind(id.to_string())
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    Ok(Json(user))
+}
+
+// Handler to update a user's name and age in place, returning the updated
+// record. Requires a valid bearer token, like `delete_user`.
+async fn update_user(
+    State(state): State<AppState>,
+    AuthUser(actor): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateUser>,
+) -> Result<Json<UserData>> {
+    println!("User {} updating user {}.", actor, id);
+
+    let result = sqlx::query("UPDATE users SET name = ?, age = ? WHERE id = ?")
+        .bind(&payload.name)
+        .bind(payload.age)
+        .bind(id.to_string())
+        .execute(&state.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Error::NotFound);
+    }
+
+    let user = sqlx::query_as::<_, UserData>("SELECT id, name, age, password FROM users WHERE id = ?")
+        .bind(id.to_string())
+        .fetch_one(&state.pool)
+        .await?;
+
+    Ok(Json(user))
+}
+
+// Handler for deleting a user by ID. Requires a valid bearer token.
+async fn delete_user(
+    State(state): State<AppState>,
+    AuthUser(actor): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<String> {
+    println!("User {} deleting user {}.", actor, id);
+
+    let result = sqlx::query("DELETE FROM users WHERE id = ?")
+        .bind(id.to_string())
+        .execute(&state.pool)
+        .await?;
+
+    // A zero-row delete means the id did not exist.
+    if result.rows_affected() > 0 {
+        Ok(format!("User with ID {} successfully deleted.", id))
+    } else {
+        Err(Error::NotFound)
+    }
+}